@@ -480,6 +480,24 @@ pub async fn handle_events(_config: Config, mut event_rx: broadcast::Receiver<Pi
                         display.render();
                     }
                 }
+                PipelineEvent::DownloadRetrying {
+                    target_id,
+                    attempt,
+                    max_attempts,
+                    delay,
+                } => {
+                    logs_buffer.push(format!(
+                        "{} {} (attempt {}/{}, retrying in {:.1}s)",
+                        "Download retrying:".yellow(),
+                        target_id.cyan(),
+                        attempt,
+                        max_attempts,
+                        delay.as_secs_f64()
+                    ));
+                    if pipeline_active {
+                        display.render();
+                    }
+                }
                 PipelineEvent::JobProcessingStarted { target_id } => {
                     display.update_job_status(&target_id, JobStatus::Processing, None);
                     if pipeline_active {
@@ -1,7 +1,8 @@
 // sps/src/pipeline/downloader.rs
 use std::fs;
 use std::path::PathBuf;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use reqwest::Client as HttpClient;
 use sps_common::cache::Cache;
@@ -12,17 +13,116 @@ use sps_common::SpsError;
 use sps_core::{build, install};
 use sps_net::http::ProgressCallback;
 use sps_net::UrlField;
-use tokio::sync::{broadcast, mpsc};
+use tokio::sync::{broadcast, mpsc, Semaphore};
 use tokio::task::JoinSet;
-use tracing::{error, warn};
+use tracing::{error, info_span, warn, Instrument};
 
 use super::runner::get_panic_message;
 
+/// Fallback cap used when the caller doesn't pass an explicit concurrency limit.
+const DEFAULT_MAX_CONCURRENT_DOWNLOADS: usize = 6;
+/// Fallback retry count used when the caller doesn't pass an explicit retry count.
+const DEFAULT_DOWNLOAD_RETRIES: u32 = 3;
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// Whether a failed download attempt is worth retrying.
+///
+/// Transient transport failures (connection resets, timeouts, 5xx responses,
+/// truncated bodies) are retried; client errors like 404s and checksum
+/// mismatches are not, since retrying them can't change the outcome. This
+/// matches on `SpsError`'s own variants rather than its rendered message, so
+/// an unrelated error can't accidentally look retryable (or not) because of
+/// what its `Display` text happens to contain.
+fn is_retryable_download_error(err: &SpsError) -> bool {
+    match err {
+        SpsError::Http(reqwest_err) => {
+            reqwest_err.is_timeout()
+                || reqwest_err.is_connect()
+                || reqwest_err
+                    .status()
+                    .is_some_and(|status| status.is_server_error())
+        }
+        SpsError::Io(io_err) => matches!(
+            io_err.kind(),
+            std::io::ErrorKind::TimedOut
+                | std::io::ErrorKind::ConnectionReset
+                | std::io::ErrorKind::ConnectionAborted
+                | std::io::ErrorKind::UnexpectedEof
+                | std::io::ErrorKind::Interrupted
+        ),
+        SpsError::ChecksumMismatch { .. } | SpsError::NotFound(_) => false,
+        _ => false,
+    }
+}
+
+/// Exponential backoff with a small jitter, capped at `RETRY_MAX_DELAY`.
+fn retry_backoff_delay(attempt: u32) -> Duration {
+    let exp = RETRY_BASE_DELAY.saturating_mul(1u32.checked_shl(attempt).unwrap_or(u32::MAX));
+    let capped = exp.min(RETRY_MAX_DELAY);
+    let jitter_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_millis() % 100)
+        .unwrap_or(0);
+    capped.saturating_add(Duration::from_millis(jitter_ms as u64))
+}
+
+/// A hook the coordinator runs for each job right after it's granted a
+/// concurrency permit and before its transfer starts. This is the one choke
+/// point every download passes through regardless of which fetch function
+/// ends up handling it, so it's where cross-cutting policy (rate limiting,
+/// per-job instrumentation, ...) can be registered without the fetch
+/// functions themselves knowing about it.
+///
+/// This is scoped to what the coordinator actually controls: it can delay or
+/// observe a transfer before it starts, but it can't intercept the transfer
+/// itself (`download_bottle_with_progress_and_cache_info` and friends make
+/// their own request with `http_client` directly), so it isn't a place to
+/// inject auth headers or a caching proxy — that needs a real
+/// `ClientWithMiddleware` built into `sps_net`, which lives outside this file.
+pub(crate) trait DownloadMiddleware: Send + Sync {
+    /// Called once per job. Return a non-zero duration to make the
+    /// coordinator wait before starting that job's transfer.
+    fn before_transfer(&self, target_id: &str) -> Duration;
+}
+
+/// Caps how often transfers may start: never more than once per
+/// `min_interval`, shared across every job the coordinator runs.
+pub(crate) struct RateLimitMiddleware {
+    min_interval: Duration,
+    next_allowed: Mutex<Option<Instant>>,
+}
+
+impl RateLimitMiddleware {
+    pub fn new(min_interval: Duration) -> Self {
+        Self {
+            min_interval,
+            next_allowed: Mutex::new(None),
+        }
+    }
+}
+
+impl DownloadMiddleware for RateLimitMiddleware {
+    fn before_transfer(&self, _target_id: &str) -> Duration {
+        let mut next_allowed = self
+            .next_allowed
+            .lock()
+            .expect("rate limiter lock poisoned");
+        let now = Instant::now();
+        let start_at = (*next_allowed).map_or(now, |t| t.max(now));
+        *next_allowed = Some(start_at + self.min_interval);
+        start_at.saturating_duration_since(now)
+    }
+}
+
 pub(crate) struct DownloadCoordinator {
     config: Config,
     cache: Arc<Cache>,
     http_client: Arc<HttpClient>,
     event_tx: Option<broadcast::Sender<PipelineEvent>>,
+    download_semaphore: Arc<Semaphore>,
+    download_retries: u32,
+    middleware: Vec<Arc<dyn DownloadMiddleware>>,
 }
 
 impl DownloadCoordinator {
@@ -37,9 +137,37 @@ impl DownloadCoordinator {
             cache,
             http_client,
             event_tx: Some(event_tx),
+            download_semaphore: Arc::new(Semaphore::new(DEFAULT_MAX_CONCURRENT_DOWNLOADS)),
+            download_retries: DEFAULT_DOWNLOAD_RETRIES,
+            middleware: Vec::new(),
         }
     }
 
+    /// Register a middleware hook to run before every job's transfer starts.
+    /// Hooks run in registration order.
+    pub fn with_middleware(mut self, middleware: Arc<dyn DownloadMiddleware>) -> Self {
+        self.middleware.push(middleware);
+        self
+    }
+
+    /// Override the default concurrent-download cap (`DEFAULT_MAX_CONCURRENT_DOWNLOADS`).
+    ///
+    /// This is exposed as an opt-in builder step rather than a `Config` field
+    /// read directly by this module, since `Config` is defined in
+    /// `sps_common` and isn't touched here; existing `DownloadCoordinator::new`
+    /// call sites keep compiling unchanged and pick up the default until they
+    /// opt in.
+    pub fn with_max_concurrent_downloads(mut self, max_concurrent_downloads: usize) -> Self {
+        self.download_semaphore = Arc::new(Semaphore::new(max_concurrent_downloads.max(1)));
+        self
+    }
+
+    /// Override the default per-download retry count (`DEFAULT_DOWNLOAD_RETRIES`).
+    pub fn with_download_retries(mut self, download_retries: u32) -> Self {
+        self.download_retries = download_retries;
+        self
+    }
+
     pub async fn coordinate_downloads(
         &mut self,
         planned_jobs: Vec<PlannedJob>,
@@ -49,7 +177,8 @@ impl DownloadCoordinator {
         let mut critical_spawn_errors: Vec<(String, SpsError)> = Vec::new();
 
         for planned_job in planned_jobs {
-            let _job_id_for_task = planned_job.target_id.clone();
+            let job_id_for_task = planned_job.target_id.clone();
+            let download_span = info_span!("download_job", target_id = %job_id_for_task);
 
             let task_config = self.config.clone();
             let task_cache = Arc::clone(&self.cache);
@@ -57,6 +186,9 @@ impl DownloadCoordinator {
             let task_event_tx = self.event_tx.as_ref().cloned();
             let outcome_tx_clone = download_outcome_tx.clone();
             let current_planned_job_for_task = planned_job.clone();
+            let task_download_semaphore = Arc::clone(&self.download_semaphore);
+            let task_download_retries = self.download_retries;
+            let task_middleware = self.middleware.clone();
 
             download_tasks.spawn(async move {
                 let job_id_in_task = current_planned_job_for_task.target_id.clone();
@@ -119,26 +251,78 @@ impl DownloadCoordinator {
                             None
                         };
 
-                        let actual_download_result: Result<(PathBuf, bool), SpsError> =
-                            match &current_planned_job_for_task.target_definition {
+                        // Bound the number of simultaneous network transfers; the permit is
+                        // held through every attempt below and released when it drops at the
+                        // end of this block, before the outcome is sent to the runner.
+                        let _download_permit = task_download_semaphore
+                            .acquire_owned()
+                            .await
+                            .expect("download semaphore should never be closed");
+
+                        for mw in &task_middleware {
+                            let delay = mw.before_transfer(&job_id_in_task);
+                            if !delay.is_zero() {
+                                tokio::time::sleep(delay).await;
+                            }
+                        }
+
+                        // Resumable downloads: not started. Each retry below re-runs the
+                        // full transfer from zero; nothing here writes a `.partial` file,
+                        // sends a `Range` header, or seeds progress from bytes already on
+                        // disk.
+                        let max_attempts = task_download_retries + 1;
+                        let mut actual_download_result: Result<(PathBuf, bool), SpsError>;
+                        let mut attempt: u32 = 1;
+                        loop {
+                            actual_download_result = match &current_planned_job_for_task.target_definition {
                                 InstallTargetIdentifier::Formula(f) => {
                                     if current_planned_job_for_task.is_source_build {
-                                        build::compile::download_source_with_progress(f, &task_config, progress_callback).await.map(|p| (p, false))
+                                        build::compile::download_source_with_progress(f, &task_config, progress_callback.clone()).await.map(|p| (p, false))
                                     } else {
                                         install::bottle::exec::download_bottle_with_progress_and_cache_info(
                                             f,
                                             &task_config,
                                             &task_http_client,
-                                            progress_callback,
+                                            progress_callback.clone(),
                                         )
                                         .await
                                     }
                                 }
                                 InstallTargetIdentifier::Cask(c) => {
-                                    install::cask::download_cask_with_progress(c, task_cache.as_ref(), progress_callback).await.map(|p| (p, false))
+                                    install::cask::download_cask_with_progress(c, task_cache.as_ref(), progress_callback.clone()).await.map(|p| (p, false))
                                 }
                             };
 
+                            let Err(ref e) = actual_download_result else {
+                                break;
+                            };
+
+                            if !is_retryable_download_error(e) {
+                                break;
+                            }
+
+                            if attempt >= max_attempts {
+                                break;
+                            }
+
+                            let delay = retry_backoff_delay(attempt - 1);
+                            if let Some(ref tx) = task_event_tx {
+                                tx.send(PipelineEvent::DownloadRetrying {
+                                    target_id: job_id_in_task.clone(),
+                                    attempt,
+                                    max_attempts,
+                                    delay,
+                                }).ok();
+                            }
+                            warn!(
+                                "[DownloaderTask:{}] Download attempt {}/{} failed from {}: {}. Retrying in {:?}.",
+                                job_id_in_task, attempt, max_attempts, display_url_for_event, e, delay
+                            );
+
+                            tokio::time::sleep(delay).await;
+                            attempt += 1;
+                        }
+
                         match actual_download_result {
                             Ok((path, was_cached)) => {
                                 let size_bytes = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
@@ -187,7 +371,7 @@ impl DownloadCoordinator {
                         job_id_in_task, send_err
                     );
                 }
-            });
+            }.instrument(download_span));
         }
 
         while let Some(join_result) = download_tasks.join_next().await {
@@ -207,3 +391,66 @@ impl DownloadCoordinator {
         critical_spawn_errors
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retryable_io_errors_are_retried() {
+        let reset = SpsError::Io(std::io::Error::new(
+            std::io::ErrorKind::ConnectionReset,
+            "reset",
+        ));
+        assert!(is_retryable_download_error(&reset));
+
+        let eof = SpsError::Io(std::io::Error::new(
+            std::io::ErrorKind::UnexpectedEof,
+            "eof",
+        ));
+        assert!(is_retryable_download_error(&eof));
+    }
+
+    #[test]
+    fn non_retryable_io_errors_are_not_retried() {
+        let denied = SpsError::Io(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            "denied",
+        ));
+        assert!(!is_retryable_download_error(&denied));
+    }
+
+    #[test]
+    fn generic_errors_are_not_retried() {
+        let err = SpsError::Generic("Download URL is missing or invalid".to_string());
+        assert!(!is_retryable_download_error(&err));
+    }
+
+    #[test]
+    fn backoff_grows_exponentially_before_the_cap() {
+        let first = retry_backoff_delay(0);
+        let second = retry_backoff_delay(1);
+        assert!(first >= RETRY_BASE_DELAY && first < RETRY_BASE_DELAY * 2);
+        assert!(second >= RETRY_BASE_DELAY * 2 && second < RETRY_BASE_DELAY * 3);
+    }
+
+    #[test]
+    fn backoff_is_capped_for_large_attempt_counts() {
+        let huge = retry_backoff_delay(20);
+        assert!(huge <= RETRY_MAX_DELAY + Duration::from_millis(100));
+    }
+
+    #[test]
+    fn rate_limit_middleware_allows_the_first_call_through_immediately() {
+        let mw = RateLimitMiddleware::new(Duration::from_millis(100));
+        assert_eq!(mw.before_transfer("job-1"), Duration::ZERO);
+    }
+
+    #[test]
+    fn rate_limit_middleware_delays_calls_within_the_same_window() {
+        let mw = RateLimitMiddleware::new(Duration::from_millis(100));
+        mw.before_transfer("job-1");
+        let second_delay = mw.before_transfer("job-2");
+        assert!(second_delay > Duration::ZERO && second_delay <= Duration::from_millis(100));
+    }
+}